@@ -4,7 +4,7 @@ use std::hash::Hash;
 
 use crate::prelude::*;
 use bevy::{
-    input::mouse::{MouseButtonInput, MouseMotion, MouseWheel},
+    input::mouse::{MouseButtonInput, MouseMotion, MouseScrollUnit, MouseWheel},
     math::Vec2,
     prelude::{Component, EventReader, MouseButton, Query, SystemLabel},
     window::CursorMoved,
@@ -19,7 +19,17 @@ pub struct MouseInputHandlingSystem;
 pub enum MouseAxisType {
     X,
     Y,
+    /// Vertical scroll wheel.
     Wheel,
+    /// Horizontal scroll wheel.
+    WheelX,
+}
+
+/// Whether a scroll event is continuous (precision) or from a discrete wheel tick.
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug, Deserialize, Serialize)]
+pub enum PrecisionScroll {
+    Yes,
+    No,
 }
 
 /// Mouse button, location and delta support for EZInput.
@@ -29,16 +39,26 @@ pub struct MouseMarker {
     pub mouse_delta: Option<Vec2>,
     pub does_mouse_location_changed_this_tick: bool,
     pub does_mouse_wheel_changed_this_tick: bool,
+    /// Unit of the last `MouseWheel` event, i.e. line-based or pixel-based.
+    pub last_scroll_unit: Option<MouseScrollUnit>,
+    /// Precision classification of the last `MouseWheel` event.
+    pub last_scroll_precision: Option<PrecisionScroll>,
+    /// Whether smoothed/inertial panning is enabled.
+    pub panning_enabled: bool,
+    /// Decay multiplier applied to `pan_delta` every tick instead of resetting it.
+    pub pan_decay: f32,
+    /// Number of consecutive motionless ticks after which `pan_delta` is reset to zero.
+    pub pan_timeout: u32,
+    /// Inertial delta accumulator used when panning is enabled.
+    pub pan_delta: Vec2,
+    /// Number of consecutive ticks since the accumulator last received new motion.
+    pub pan_idle_ticks: u32,
 }
 
 impl MouseMarker {
-    /// Change the current mouse location and delta and set the last input source to Mouse.
-    pub fn set_mouse_location<Keys>(
-        &mut self,
-        view: &mut InputView<Keys>,
-        position: Vec2,
-        delta: Vec2,
-    ) where
+    /// Set the current absolute mouse position and set the last input source to Mouse.
+    pub fn set_mouse_position<Keys>(&mut self, view: &mut InputView<Keys>, position: Vec2)
+    where
         Keys: BindingTypeView,
     {
         let state = PressState::Pressed {
@@ -55,23 +75,57 @@ impl MouseMarker {
             position.y,
             state,
         );
+
+        self.mouse_position = Some(position);
+        self.does_mouse_location_changed_this_tick = true;
+        view.last_input_source = Some(InputSource::Mouse);
+    }
+
+    /// Accumulate a relative mouse movement delta and set the last input source to Mouse.
+    pub fn set_mouse_delta<Keys>(&mut self, view: &mut InputView<Keys>, delta: Vec2)
+    where
+        Keys: BindingTypeView,
+    {
+        let state = PressState::Pressed {
+            started_pressing_instant: None,
+        };
+
+        self.mouse_delta = Some(self.mouse_delta.unwrap_or_default() + delta);
+
+        let axis_delta = if self.panning_enabled {
+            self.pan_delta += delta;
+            self.pan_idle_ticks = 0;
+            self.pan_delta
+        } else {
+            self.mouse_delta.unwrap_or_default()
+        };
+
         view.set_axis_value(
             InputReceiver::MouseAxisDelta(MouseAxisType::X),
-            delta.x,
+            axis_delta.x,
             state,
         );
         view.set_axis_value(
             InputReceiver::MouseAxisDelta(MouseAxisType::Y),
-            delta.y,
+            axis_delta.y,
             state,
         );
 
-        self.mouse_delta = Some(delta);
-        self.mouse_position = Some(position);
         self.does_mouse_location_changed_this_tick = true;
         view.last_input_source = Some(InputSource::Mouse);
     }
 
+    /// Enable or disable smoothed, inertial panning with the given decay and timeout.
+    pub fn set_panning(&mut self, enabled: bool, decay: f32, timeout: u32) {
+        self.panning_enabled = enabled;
+        self.pan_decay = decay;
+        self.pan_timeout = timeout;
+        if !enabled {
+            self.pan_delta = Vec2::ZERO;
+            self.pan_idle_ticks = 0;
+        }
+    }
+
     /// Tick the mouse by stop moving the axis when released.
     pub fn tick_mouse<Keys>(&mut self, view: &mut InputView<Keys>)
     where
@@ -94,13 +148,29 @@ impl MouseMarker {
             PressState::Released,
         );
         view.set_axis_value(
-            InputReceiver::MouseAxisDelta(MouseAxisType::X),
+            InputReceiver::MouseAxis(MouseAxisType::WheelX),
             0.,
             PressState::Released,
         );
+        let pan_delta = if self.panning_enabled {
+            self.pan_idle_ticks += 1;
+            if self.pan_idle_ticks >= self.pan_timeout {
+                self.pan_delta = Vec2::ZERO;
+            } else {
+                self.pan_delta *= self.pan_decay;
+            }
+            self.pan_delta
+        } else {
+            Vec2::ZERO
+        };
+        view.set_axis_value(
+            InputReceiver::MouseAxisDelta(MouseAxisType::X),
+            pan_delta.x,
+            PressState::Released,
+        );
         view.set_axis_value(
             InputReceiver::MouseAxisDelta(MouseAxisType::Y),
-            0.,
+            pan_delta.y,
             PressState::Released,
         );
         view.set_axis_value(
@@ -126,19 +196,91 @@ impl MouseMarker {
         view.set_key_receiver_state(InputReceiver::MouseButton(button), state);
     }
 
-    /// Set the mouse wheel state and set the last input source to Mouse.
+    /// Set the mouse wheel state for both axes and set the last input source to Mouse.
     pub fn set_mouse_wheel_state<Keys>(
         &mut self,
         view: &mut InputView<Keys>,
+        x: f32,
         y: f32,
-        state: PressState,
+        unit: MouseScrollUnit,
     ) where
         Keys: BindingTypeView,
     {
         view.last_input_source = Some(InputSource::Mouse);
-        view.set_axis_value(InputReceiver::MouseAxis(MouseAxisType::Wheel), y, state);
+        view.set_axis_value(
+            InputReceiver::MouseAxis(MouseAxisType::Wheel),
+            y,
+            press_state_for_scroll(y),
+        );
+        view.set_axis_value(
+            InputReceiver::MouseAxis(MouseAxisType::WheelX),
+            x,
+            press_state_for_scroll(x),
+        );
+        self.last_scroll_unit = Some(unit);
+        self.last_scroll_precision = Some(classify_scroll_precision(unit, x, y));
         self.does_mouse_wheel_changed_this_tick = true;
     }
+
+    // Headless mocking API: drives the same primitives `mouse_input_system` uses, so tests
+    // without a windowing backend can assert on mouse state deterministically.
+
+    /// Mock a `MouseButtonInput` event for the given button and state.
+    pub fn mock_button<Keys>(
+        &mut self,
+        view: &mut InputView<Keys>,
+        button: MouseButton,
+        state: PressState,
+    ) where
+        Keys: BindingTypeView,
+    {
+        self.set_mouse_button_state(view, button, state);
+    }
+
+    /// Mock a `CursorMoved` event carrying `position` together with a `MouseMotion` event
+    /// carrying `delta`, as if both had arrived in the same tick.
+    pub fn mock_move<Keys>(&mut self, view: &mut InputView<Keys>, position: Vec2, delta: Vec2)
+    where
+        Keys: BindingTypeView,
+    {
+        self.set_mouse_position(view, position);
+        self.set_mouse_delta(view, delta);
+    }
+
+    /// Mock a `MouseWheel` event with the given scroll deltas and unit.
+    pub fn mock_wheel<Keys>(
+        &mut self,
+        view: &mut InputView<Keys>,
+        x: f32,
+        y: f32,
+        unit: MouseScrollUnit,
+    ) where
+        Keys: BindingTypeView,
+    {
+        self.set_mouse_wheel_state(view, x, y, unit);
+    }
+
+    /// Mock the end-of-frame tick that `mouse_input_system` runs before reading events.
+    pub fn mock_tick<Keys>(&mut self, view: &mut InputView<Keys>)
+    where
+        Keys: BindingTypeView,
+    {
+        self.tick_mouse(view);
+    }
+}
+
+/// Classify a scroll event as precision (continuous) or tick-based (discrete).
+fn classify_scroll_precision(unit: MouseScrollUnit, x: f32, y: f32) -> PrecisionScroll {
+    match unit {
+        MouseScrollUnit::Pixel => PrecisionScroll::Yes,
+        MouseScrollUnit::Line => {
+            if x.fract() == 0. && y.fract() == 0. {
+                PrecisionScroll::No
+            } else {
+                PrecisionScroll::Yes
+            }
+        }
+    }
 }
 
 /// Input system responsible for handling mouse input and setting the button state for each updated button and axis.
@@ -156,21 +298,132 @@ pub(crate) fn mouse_input_system<Keys>(
         let mouse_svc = mouse_svc.as_mut();
         mouse_svc.tick_mouse(view);
 
-        for (abs_position, delta) in cursor_rd.iter().zip(mtn_rd.iter()) {
-            mouse_svc.set_mouse_location(view, abs_position.position, delta.delta);
+        for ev in cursor_rd.iter() {
+            mouse_svc.set_mouse_position(view, ev.position);
+        }
+        for ev in mtn_rd.iter() {
+            mouse_svc.set_mouse_delta(view, ev.delta);
         }
         for ev in btn_rd.iter() {
             mouse_svc.set_mouse_button_state(view, ev.button, ev.state.into());
         }
         for ev in wheel_rd.iter() {
-            let state = if ev.y > 0. {
-                PressState::Pressed {
-                    started_pressing_instant: None,
-                }
-            } else {
-                PressState::Released    
-            };
-            mouse_svc.set_mouse_wheel_state(view, ev.y, state);
+            mouse_svc.set_mouse_wheel_state(view, ev.x, ev.y, ev.unit);
+        }
+    }
+}
+
+/// A wheel axis is pressed whenever it has scrolled at all this tick, in either direction.
+fn press_state_for_scroll(magnitude: f32) -> PressState {
+    if magnitude != 0. {
+        PressState::Pressed {
+            started_pressing_instant: None,
         }
+    } else {
+        PressState::Released
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(PartialEq, Eq, Hash, Clone, Copy, Debug, Deserialize, Serialize)]
+    enum TestKeys {
+        Action,
+    }
+
+    fn axis_value(view: &mut InputView<TestKeys>, axis: MouseAxisType) -> f32 {
+        view.descriptor_or_insert(InputReceiver::MouseAxis(axis))
+            .axis
+            .value
+    }
+
+    fn axis_delta_value(view: &mut InputView<TestKeys>, axis: MouseAxisType) -> f32 {
+        view.descriptor_or_insert(InputReceiver::MouseAxisDelta(axis))
+            .axis
+            .value
+    }
+
+    #[test]
+    fn mock_move_updates_position_and_delta_independently() {
+        let mut view = InputView::<TestKeys>::default();
+        let mut mouse = MouseMarker::default();
+
+        mouse.mock_tick(&mut view);
+        mouse.mock_move(&mut view, Vec2::new(10., 20.), Vec2::new(1., -2.));
+
+        assert_eq!(mouse.mouse_position, Some(Vec2::new(10., 20.)));
+        assert_eq!(mouse.mouse_delta, Some(Vec2::new(1., -2.)));
+        assert_eq!(axis_value(&mut view, MouseAxisType::X), 10.);
+        assert_eq!(axis_value(&mut view, MouseAxisType::Y), 20.);
+        assert_eq!(axis_delta_value(&mut view, MouseAxisType::X), 1.);
+        assert_eq!(axis_delta_value(&mut view, MouseAxisType::Y), -2.);
+        assert_eq!(view.last_input_source, Some(InputSource::Mouse));
+    }
+
+    #[test]
+    fn mock_wheel_negative_scroll_still_counts_as_pressed() {
+        let mut view = InputView::<TestKeys>::default();
+        let mut mouse = MouseMarker::default();
+
+        mouse.mock_tick(&mut view);
+        mouse.mock_wheel(&mut view, 0., -1., MouseScrollUnit::Line);
+
+        let pressed = PressState::Pressed {
+            started_pressing_instant: None,
+        };
+        assert_eq!(
+            view.descriptor_or_insert(InputReceiver::MouseAxis(MouseAxisType::Wheel))
+                .axis
+                .press,
+            pressed
+        );
+        assert_eq!(mouse.last_scroll_unit, Some(MouseScrollUnit::Line));
+    }
+
+    #[test]
+    fn mock_wheel_classifies_precision_by_unit_and_magnitude() {
+        let mut view = InputView::<TestKeys>::default();
+        let mut mouse = MouseMarker::default();
+
+        mouse.mock_wheel(&mut view, 0., 1., MouseScrollUnit::Pixel);
+        assert_eq!(mouse.last_scroll_precision, Some(PrecisionScroll::Yes));
+
+        mouse.mock_wheel(&mut view, 0., 1., MouseScrollUnit::Line);
+        assert_eq!(mouse.last_scroll_precision, Some(PrecisionScroll::No));
+
+        mouse.mock_wheel(&mut view, 0., 0.5, MouseScrollUnit::Line);
+        assert_eq!(mouse.last_scroll_precision, Some(PrecisionScroll::Yes));
+    }
+
+    #[test]
+    fn panning_decays_to_zero_after_timeout() {
+        let mut view = InputView::<TestKeys>::default();
+        let mut mouse = MouseMarker::default();
+        mouse.set_panning(true, 0.5, 2);
+
+        mouse.mock_move(&mut view, Vec2::ZERO, Vec2::new(10., 0.));
+        assert_eq!(mouse.pan_delta, Vec2::new(10., 0.));
+
+        mouse.mock_tick(&mut view);
+        assert_eq!(mouse.pan_delta, Vec2::new(5., 0.));
+        assert_eq!(axis_delta_value(&mut view, MouseAxisType::X), 5.);
+
+        mouse.mock_tick(&mut view);
+        assert_eq!(mouse.pan_delta, Vec2::ZERO);
+        assert_eq!(axis_delta_value(&mut view, MouseAxisType::X), 0.);
+    }
+
+    #[test]
+    fn mock_button_sets_last_input_source() {
+        let mut view = InputView::<TestKeys>::default();
+        let mut mouse = MouseMarker::default();
+
+        let pressed = PressState::Pressed {
+            started_pressing_instant: None,
+        };
+        mouse.mock_button(&mut view, MouseButton::Left, pressed);
+        assert_eq!(view.last_input_source, Some(InputSource::Mouse));
     }
 }